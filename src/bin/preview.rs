@@ -0,0 +1,120 @@
+use eframe::egui;
+use skia_safe::{AlphaType, ColorType, Data, Image as SkImage, ImageInfo};
+use soc_credit_bot_rs::config::Suffixes;
+use soc_credit_bot_rs::layout::RenderParams;
+use soc_credit_bot_rs::{format_chinese_number, format_latin_number, render_raw_number_with_params};
+
+struct PreviewApp {
+    number: String,
+    params: RenderParams,
+}
+
+impl Default for PreviewApp {
+    fn default() -> Self {
+        PreviewApp {
+            number: "12345".to_string(),
+            params: RenderParams::default(),
+        }
+    }
+}
+
+fn decode_to_color_image(webp: &[u8]) -> Option<egui::ColorImage> {
+    let image = SkImage::from_encoded(Data::new_copy(webp))?;
+    let info = ImageInfo::new(image.dimensions(), ColorType::RGBA8888, AlphaType::Unpremul, None);
+    let row_bytes = (image.width() * 4) as usize;
+    let mut pixels = vec![0u8; row_bytes * image.height() as usize];
+
+    if !image.read_pixels(&info, &mut pixels, row_bytes, (0, 0), skia_safe::image::CachingHint::Disallow) {
+        return None;
+    }
+
+    Some(egui::ColorImage::from_rgba_unmultiplied([image.width() as usize, image.height() as usize], &pixels))
+}
+
+impl eframe::App for PreviewApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::SidePanel::left("controls").show(ctx, |ui| {
+            ui.heading("Number");
+            ui.text_edit_singleline(&mut self.number);
+
+            ui.separator();
+            ui.heading("CJK font sizes");
+            ui.add(egui::Slider::new(&mut self.params.cjk_font_large, 10.0..=80.0).text("large (<=4 chars)"));
+            ui.add(egui::Slider::new(&mut self.params.cjk_font_medium, 10.0..=80.0).text("medium (5 chars)"));
+            ui.add(egui::Slider::new(&mut self.params.cjk_font_small, 10.0..=80.0).text("small (6 chars)"));
+            ui.add(egui::Slider::new(&mut self.params.cjk_font_pico, 10.0..=80.0).text("pico (7+ chars)"));
+
+            ui.separator();
+            ui.heading("Latin font sizes");
+            ui.add(egui::Slider::new(&mut self.params.latin_font_large, 10.0..=60.0).text("large"));
+            ui.add(egui::Slider::new(&mut self.params.latin_font_small, 10.0..=60.0).text("small"));
+
+            ui.separator();
+            ui.heading("Offsets");
+            ui.add(egui::Slider::new(&mut self.params.text_x, 0..=512).text("text x"));
+            ui.add(egui::Slider::new(&mut self.params.chinese_y, 0..=174).text("chinese y"));
+            ui.add(egui::Slider::new(&mut self.params.chinese_y_pico, 0..=174).text("chinese y (pico)"));
+            ui.add(egui::Slider::new(&mut self.params.chinese_y_split_top, 0..=174).text("chinese y (split top)"));
+            ui.add(egui::Slider::new(&mut self.params.chinese_y_split_bottom, 0..=174).text("chinese y (split bottom)"));
+            ui.add(egui::Slider::new(&mut self.params.latin_y_large, 0..=174).text("latin y (large)"));
+            ui.add(egui::Slider::new(&mut self.params.latin_y_small, 0..=174).text("latin y (small)"));
+
+            let (mut shadow_x, mut shadow_y) = self.params.shadow_offset;
+            ui.add(egui::Slider::new(&mut shadow_x, -20..=20).text("shadow x"));
+            ui.add(egui::Slider::new(&mut shadow_y, -20..=20).text("shadow y"));
+            self.params.shadow_offset = (shadow_x, shadow_y);
+
+            ui.separator();
+            ui.heading("Char-count thresholds");
+            ui.add(egui::Slider::new(&mut self.params.chars_large_max, 1..=20).text("large max"));
+            ui.add(egui::Slider::new(&mut self.params.chars_medium, 1..=20).text("medium"));
+            ui.add(egui::Slider::new(&mut self.params.chars_small, 1..=20).text("small"));
+            ui.add(egui::Slider::new(&mut self.params.chars_pico, 1..=20).text("pico"));
+            ui.add(egui::Slider::new(&mut self.params.chars_split_max, 1..=30).text("split max"));
+            ui.add(egui::Slider::new(&mut self.params.latin_chars_small_threshold, 1..=20).text("latin small"));
+            ui.add(egui::Slider::new(&mut self.params.latin_chars_suffix_threshold, 1..=20).text("latin short suffix"));
+
+            if ui.button("Reset to defaults").clicked() {
+                self.params = RenderParams::default();
+            }
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let number: std::result::Result<i32, _> = self.number.parse();
+
+            match number {
+                Err(_) => {
+                    ui.colored_label(egui::Color32::RED, format!("'{}' is not a whole number", self.number));
+                }
+                Ok(number) => {
+                    ui.label(format!("Chinese: {}", format_chinese_number(number).unwrap_or_else(|| "-".into())));
+                    ui.label(format!("Latin: {}", format_latin_number(number).unwrap_or_else(|| "-".into())));
+                    ui.separator();
+
+                    match render_raw_number_with_params(number, &Suffixes::default(), &self.params) {
+                        Err(e) => {
+                            ui.colored_label(egui::Color32::RED, e.to_string());
+                        }
+                        Ok(webp) => match decode_to_color_image(&webp) {
+                            Some(color_image) => {
+                                let texture = ctx.load_texture("preview", color_image, egui::TextureOptions::NEAREST);
+                                ui.image(&texture, texture.size_vec2());
+                            }
+                            None => {
+                                ui.colored_label(egui::Color32::RED, "rendered image could not be decoded");
+                            }
+                        },
+                    }
+                }
+            }
+        });
+    }
+}
+
+fn main() -> eframe::Result<()> {
+    eframe::run_native(
+        "Soc. Credit sticker preview",
+        eframe::NativeOptions::default(),
+        Box::new(|_cc| Box::new(PreviewApp::default())),
+    )
+}
@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(author, version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Connect to Telegram and serve inline queries for every bot in the config file.
+    Serve {
+        /// Path to the JSON config file (api_id/api_hash/bots).
+        config: PathBuf,
+    },
+    /// Render a single sticker offline, without connecting to Telegram.
+    Render {
+        /// The number to render, e.g. 12345 or -42.
+        #[arg(allow_hyphen_values = true)]
+        number: i32,
+
+        /// Where to write the WEBP image. Defaults to stdout.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+/// Top-level runtime configuration, loaded from the JSON file passed on the command line.
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    pub api_id: i32,
+    pub api_hash: String,
+    pub bots: Vec<BotConfig>,
+}
+
+/// One Telegram bot identity. Each entry gets its own `Client` connect loop and session file,
+/// so a single binary can run several themed personas (e.g. staging/prod) side by side.
+#[derive(Debug, Deserialize)]
+pub struct BotConfig {
+    pub token: String,
+    pub session_file: String,
+
+    #[serde(default)]
+    pub chinese_suffix: Option<String>,
+    #[serde(default)]
+    pub latin_suffix_short: Option<String>,
+    #[serde(default)]
+    pub latin_suffix_full: Option<String>,
+
+    /// Max number of updates this bot renders concurrently. Defaults to `DEFAULT_CONCURRENCY`
+    /// in `main.rs` when unset.
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+}
+
+/// Resolved sticker suffix strings, falling back to the built-in defaults when a bot's
+/// config doesn't override them.
+pub struct Suffixes {
+    pub chinese: String,
+    pub latin_short: String,
+    pub latin_full: String,
+}
+
+impl BotConfig {
+    pub fn suffixes(&self) -> Suffixes {
+        Suffixes {
+            chinese: self.chinese_suffix.clone().unwrap_or_else(|| crate::DEFAULT_CHINESE_SUFFIX.to_string()),
+            latin_short: self.latin_suffix_short.clone().unwrap_or_else(|| crate::DEFAULT_LATIN_SUFFIX_SHORT.to_string()),
+            latin_full: self.latin_suffix_full.clone().unwrap_or_else(|| crate::DEFAULT_LATIN_SUFFIX_FULL.to_string()),
+        }
+    }
+}
+
+impl Default for Suffixes {
+    fn default() -> Self {
+        Suffixes {
+            chinese: crate::DEFAULT_CHINESE_SUFFIX.to_string(),
+            latin_short: crate::DEFAULT_LATIN_SUFFIX_SHORT.to_string(),
+            latin_full: crate::DEFAULT_LATIN_SUFFIX_FULL.to_string(),
+        }
+    }
+}
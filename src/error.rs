@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+/// Everything that can go wrong turning an inline query into a sticker, in terms a user
+/// can be shown directly (see `Display`).
+#[derive(Debug, Error)]
+pub enum StickerError {
+    #[error("'{text}' is not a whole number")]
+    ParseError {
+        text: String,
+        #[source]
+        source: std::num::ParseIntError,
+    },
+
+    #[error("enter a whole number between {min} and {max}")]
+    NumberOutOfRange { min: i32, max: i32 },
+
+    #[error("failed to render the sticker")]
+    RenderFailed,
+
+    #[error("failed to upload the sticker: {0}")]
+    UploadFailed(String),
+}
@@ -0,0 +1,80 @@
+/// The font sizes, offsets and char-count thresholds of `render`'s Skia layout, pulled out so
+/// the `preview` tool can tune them live instead of edit-recompile-run-on-Telegram. The canvas
+/// size and the latin y shift used with the single-line pico bucket stay fixed in `render`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderParams {
+    pub cjk_font_large: f32,
+    pub cjk_font_medium: f32,
+    pub cjk_font_small: f32,
+    pub cjk_font_pico: f32,
+
+    pub latin_font_large: f32,
+    pub latin_font_small: f32,
+
+    /// Shared x offset every line of text is drawn at.
+    pub text_x: i32,
+    /// y offset for the large/medium/small Chinese font buckets (up to `chars_small`).
+    pub chinese_y: i32,
+    /// y offset for the Chinese pico bucket when drawn on a single line (`chars_pico`).
+    pub chinese_y_pico: i32,
+    /// y offsets for the two-line Chinese pico layouts (counts above `chars_pico`, both up to
+    /// and past `chars_split_max`).
+    pub chinese_y_split_top: i32,
+    pub chinese_y_split_bottom: i32,
+
+    pub latin_y_large: i32,
+    pub latin_y_small: i32,
+
+    pub shadow_offset: (i32, i32),
+
+    /// Chinese char counts <= this use `cjk_font_large`.
+    pub chars_large_max: usize,
+    /// Chinese char count using `cjk_font_medium`.
+    pub chars_medium: usize,
+    /// Chinese char count using `cjk_font_small`.
+    pub chars_small: usize,
+    /// Chinese char count using `cjk_font_pico` on one line.
+    pub chars_pico: usize,
+    /// Chinese char counts <= this (and > `chars_pico`) use `cjk_font_pico` on two lines;
+    /// above it, the number and suffix are split across the two lines instead.
+    pub chars_split_max: usize,
+
+    /// Latin number char counts greater than this switch from `latin_font_large` to `latin_font_small`.
+    pub latin_chars_small_threshold: usize,
+    /// Latin number char counts greater than this use the short suffix instead of the full one.
+    pub latin_chars_suffix_threshold: usize,
+}
+
+impl Default for RenderParams {
+    fn default() -> Self {
+        RenderParams {
+            cjk_font_large: 40.0,
+            cjk_font_medium: 36.0,
+            cjk_font_small: 32.0,
+            cjk_font_pico: 28.0,
+
+            latin_font_large: 29.0,
+            latin_font_small: 24.0,
+
+            text_x: 160,
+            chinese_y: 140,
+            chinese_y_pico: 135,
+            chinese_y_split_top: 110,
+            chinese_y_split_bottom: 145,
+
+            latin_y_large: 80,
+            latin_y_small: 75,
+
+            shadow_offset: (4, 4),
+
+            chars_large_max: 4,
+            chars_medium: 5,
+            chars_small: 6,
+            chars_pico: 7,
+            chars_split_max: 11,
+
+            latin_chars_small_threshold: 4,
+            latin_chars_suffix_threshold: 7,
+        }
+    }
+}
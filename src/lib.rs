@@ -0,0 +1,210 @@
+pub mod config;
+pub mod error;
+pub mod layout;
+
+use std::fs::read;
+use skia_safe::{Canvas, Color4f, ColorSpace, Data, EncodedImageFormat, Font, Image, Paint, Surface, TextBlob, TextEncoding, Typeface};
+
+use config::Suffixes;
+use error::StickerError;
+use layout::RenderParams;
+
+const DIGITS: &str = "一二三四五六七八九";
+const EXPONENTS: &str = "十百千";
+const ZERO_MARK: char = '零';
+const MYRIAD_MARK: &str = "万";
+const TWO_MARK_FOR_THOUSANDS: char = '两';
+pub const DEFAULT_CHINESE_SUFFIX: &str = "社会信用";
+pub const DEFAULT_LATIN_SUFFIX_SHORT: &str = "Soc. Credit";
+pub const DEFAULT_LATIN_SUFFIX_FULL: &str = "Social Credit";
+
+/// Smallest magnitude `render_raw_number` will draw a sticker for.
+pub const MIN_NUMBER: i32 = 1;
+/// Largest magnitude `render_raw_number` will draw a sticker for.
+pub const MAX_NUMBER: i32 = 99_999_999;
+
+pub fn format_latin_number(number: i32) -> Option<String>  {
+    let abs = number.abs();
+
+    if abs == 0 || abs >= 100000000 {
+        return None;
+    }
+
+    let max_exp = {
+        let mut cur = abs;
+        let mut max_exp = 0;
+
+        while cur > 0 && (cur % 10 == 0) {
+            cur /= 10;
+            max_exp += 1
+        }
+
+        max_exp
+    };
+
+    match max_exp / 3 {
+        0 => Some(abs.to_string()),
+        1 => Some((abs / 1000).to_string() + "k"),
+        2 => Some((abs / 1000000).to_string() + "m"),
+        _ => None
+    }
+}
+
+pub fn format_chinese_number(number: i32) -> Option<String> {
+    let mut abs = number.abs();
+
+    if abs == 0 || abs >= 100000000 /*一亿*/ {
+        return None
+    }
+
+    if abs > 10000 /*一万*/ {
+        let lower_part = {
+            let lower_part_int = abs % 10000;
+
+            if lower_part_int == 0 {
+                "".into()
+            } else {
+                format_chinese_number(lower_part_int)?
+            }
+        };
+
+        let upper_part = {
+            let upper_part_int = abs / 10000;
+            format_chinese_number(upper_part_int)?
+        };
+
+        return Some(format!("{}{}{}", upper_part, MYRIAD_MARK, lower_part))
+    }
+
+    let mut exp = 0;
+    let mut result: String = "".into();
+
+    while abs > 0 {
+        let digit = abs % 10;
+
+        if digit == 0 {
+            if !result.is_empty() && result.chars().nth(0).unwrap() != ZERO_MARK {
+                result = ZERO_MARK.to_string() + &result
+            }
+        } else {
+            let digit_char = match exp {
+                3 if digit == 2 => TWO_MARK_FOR_THOUSANDS,
+                _ => DIGITS.chars().nth((digit - 1) as usize)?
+            };
+
+            let exponent = if exp == 0 { "".into() } else { EXPONENTS.chars().nth(exp - 1)?.to_string() };
+
+            result = format!("{}{}{}", digit_char, exponent, result);
+        }
+
+        abs /= 10;
+        exp += 1;
+    }
+
+    Some(result)
+}
+
+pub fn render(base: Image, latin_number: String, chinese_number: String, suffixes: &Suffixes, params: &RenderParams) -> Option<Vec<u8>> {
+    let mut surface = Surface::new_raster_n32_premul((512, 174))?;
+    let mut canvas = surface.canvas();
+
+    let srgb = ColorSpace::new_srgb();
+    let white_paint = Paint::new(Color4f::new(1.0, 1.0, 1.0, 1.0), &srgb);
+    let black_paint = Paint::new(Color4f::new(0.0, 0.0, 0.0, 1.0), &srgb);
+
+    let (shadow_x, shadow_y) = params.shadow_offset;
+    let mut render_shadowed = |canvas: &mut Canvas, text: String, font: &Font, x: i32, y: i32| -> Option<()> {
+        let tl = TextBlob::from_text(&text.to_bytes(), TextEncoding::UTF8, &font)?;
+
+        canvas.draw_text_blob(&tl, (x + shadow_x, y + shadow_y), &black_paint);
+        canvas.draw_text_blob(&tl, (x, y), &white_paint);
+
+        Some(())
+    };
+
+    canvas.draw_image(base, (0, 0), None);
+
+    let cjkTypefaceData = read("3rdparty/BIZ-UDGothicR.ttc").ok()?;
+    let cjkTypeface = Typeface::from_data(Data::new_copy(&cjkTypefaceData), None)?;
+    let cjkFontLarge = Font::new(&cjkTypeface, Some(params.cjk_font_large.into()));
+    let cjkFontMedium = Font::new(&cjkTypeface, Some(params.cjk_font_medium.into()));
+    let cjkFontSmall = Font::new(&cjkTypeface, Some(params.cjk_font_small.into()));
+    let cjkFontPico = Font::new(&cjkTypeface, Some(params.cjk_font_pico.into()));
+
+    let chars_count = chinese_number.chars().count();
+    let x = params.text_x;
+
+    let latinYComp = if chars_count <= params.chars_large_max {
+        render_shadowed(canvas, chinese_number + &suffixes.chinese, &cjkFontLarge, x, params.chinese_y);
+        0
+    } else if chars_count == params.chars_medium {
+        render_shadowed(canvas, chinese_number + &suffixes.chinese, &cjkFontMedium, x, params.chinese_y);
+        0
+    } else if chars_count == params.chars_small {
+        render_shadowed(canvas, chinese_number + &suffixes.chinese, &cjkFontSmall, x, params.chinese_y);
+        0
+    } else if chars_count == params.chars_pico {
+        render_shadowed(canvas, chinese_number + &suffixes.chinese, &cjkFontPico, x, params.chinese_y_pico);
+        10
+    } else if chars_count <= params.chars_split_max {
+        render_shadowed(canvas, chinese_number, &cjkFontPico, x, params.chinese_y_split_top);
+        render_shadowed(canvas, suffixes.chinese.clone(), &cjkFontPico, x, params.chinese_y_split_bottom);
+        0
+    } else {
+        let mut splitPosition = (chars_count + suffixes.chinese.chars().count()) / 2;
+        let firstWrappedChar = chinese_number.chars().nth(splitPosition)?;
+
+        if !DIGITS.contains(firstWrappedChar) && firstWrappedChar != ZERO_MARK && firstWrappedChar != TWO_MARK_FOR_THOUSANDS {
+            splitPosition += 1; // try not to break periods
+        }
+
+        let splitByte = chinese_number.char_indices().nth(splitPosition).map_or(chinese_number.len(), |(i, _)| i);
+        let (lp, rp) = chinese_number.split_at(splitByte);
+        render_shadowed(canvas, lp.into(), &cjkFontPico, x, params.chinese_y_split_top);
+        render_shadowed(canvas, rp.to_string() + &suffixes.chinese, &cjkFontPico, x, params.chinese_y_split_bottom);
+        0
+    };
+
+    let latinTypefaceData = read("3rdparty/VCR_OSD_MONO_1.001.ttf").ok()?;
+    let latinTypeface = Typeface::from_data(Data::new_copy(&latinTypefaceData), None)?;
+    let latinFontLarge = Font::new(&latinTypeface, Some(params.latin_font_large.into()));
+    let latinFontSmall = Font::new(&latinTypeface, Some(params.latin_font_small.into()));
+
+    // render latin number
+    let latin_chars_count = latin_number.chars().count();
+    let latinSuffix = if latin_chars_count > params.latin_chars_suffix_threshold { &suffixes.latin_short } else { &suffixes.latin_full };
+    let (latinFont, latinY) = if latin_chars_count > params.latin_chars_small_threshold { (latinFontSmall, params.latin_y_small) } else { (latinFontLarge, params.latin_y_large) };
+
+    render_shadowed(canvas, latin_number + " " + latinSuffix, &latinFont, x, latinY + latinYComp);
+
+    let image = surface.image_snapshot();
+    let data = image.encode_to_data(EncodedImageFormat::WEBP)?;
+
+    Some(data.as_bytes().to_bytes())
+}
+
+pub fn render_number(orig_number: i32, sig: &str, base: Image, suffixes: &Suffixes, params: &RenderParams) -> Option<Vec<u8>> {
+    let chinese_number = format_chinese_number(orig_number)?;
+    let latin_number = format_latin_number(orig_number)?;
+
+    render(base, sig.to_string() + latin_number.as_str(), sig.to_string() + chinese_number.as_str(), suffixes, params)
+}
+
+pub fn render_raw_number(amount: i32, suffixes: &Suffixes) -> Result<Vec<u8>, StickerError> {
+    render_raw_number_with_params(amount, suffixes, &RenderParams::default())
+}
+
+pub fn render_raw_number_with_params(amount: i32, suffixes: &Suffixes, params: &RenderParams) -> Result<Vec<u8>, StickerError> {
+    let abs = amount.unsigned_abs();
+    if abs < MIN_NUMBER as u32 || abs > MAX_NUMBER as u32 {
+        return Err(StickerError::NumberOutOfRange { min: MIN_NUMBER, max: MAX_NUMBER });
+    }
+
+    let base_path = if amount < 0 { "3rdparty/minus.png" } else { "3rdparty/plus.png" };
+    let sig = if amount < 0 { "-" } else { "+" };
+
+    let base_data = read(base_path).map_err(|_| StickerError::RenderFailed)?;
+    let base = Image::from_encoded(Data::new_copy(&base_data)).ok_or(StickerError::RenderFailed)?;
+
+    render_number(amount, sig, base, suffixes, params).ok_or(StickerError::RenderFailed)
+}
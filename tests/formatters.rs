@@ -0,0 +1,48 @@
+use soc_credit_bot_rs::{format_chinese_number, format_latin_number};
+
+#[test]
+fn chinese_number_table() {
+    let cases = [
+        (1, "一"),
+        (10, "一十"),
+        (10203, "一万二百零三"),
+        (2000, "两千"),
+        (99999999, "九千九百九十九万九千九百九十九"),
+        (-1, "一"),
+        (-10203, "一万二百零三"),
+    ];
+
+    for (input, expected) in cases {
+        assert_eq!(format_chinese_number(input).as_deref(), Some(expected), "input = {}", input);
+    }
+}
+
+#[test]
+fn chinese_number_out_of_range() {
+    assert_eq!(format_chinese_number(0), None);
+    assert_eq!(format_chinese_number(100000000), None);
+    assert_eq!(format_chinese_number(-100000000), None);
+}
+
+#[test]
+fn latin_number_table() {
+    let cases = [
+        (1, "1"),
+        (10, "10"),
+        (10203, "10203"),
+        (2000, "2k"),
+        (1000000, "1m"),
+        (5000000, "5m"),
+        (-42, "42"),
+    ];
+
+    for (input, expected) in cases {
+        assert_eq!(format_latin_number(input).as_deref(), Some(expected), "input = {}", input);
+    }
+}
+
+#[test]
+fn latin_number_out_of_range() {
+    assert_eq!(format_latin_number(0), None);
+    assert_eq!(format_latin_number(100000000), None);
+}
@@ -0,0 +1,70 @@
+use soc_credit_bot_rs::config::Suffixes;
+use soc_credit_bot_rs::render_raw_number;
+use skia_safe::{ColorType, Data, Image};
+
+const FIXTURES_DIR: &str = "tests/fixtures";
+const MAX_CHANNEL_DELTA: u8 = 2;
+
+// One number per font-size bucket plus the split-position wrapping branch, so a layout
+// regression in any bucket shows up as an image diff. `render`'s branch is picked by the
+// *formatted* Chinese numeral length plus the sign character, not by the input's decimal
+// digit count, so these were chosen by running each candidate through `format_chinese_number`
+// rather than guessed from the input's magnitude.
+const CASES: &[(&str, i32)] = &[
+    ("large", 11),        // "一十一" -> 3 chars + sign = 4, the chars_large_max boundary
+    ("medium", 101),      // "一百零一" -> 4 chars + sign = 5
+    ("small", 111),       // "一百一十一" -> 5 chars + sign = 6
+    ("pico", 1011),       // "一千零一十一" -> 6 chars + sign = 7
+    ("two_line", 1111),   // "一千一百一十一" -> 7 chars + sign = 8, inside the 8-11 two-line range
+    ("split_position", 111122), // "一十一万一千一百二十二" -> 11 chars + sign = 12, past chars_split_max
+];
+
+fn fixture_path(name: &str) -> std::path::PathBuf {
+    std::path::Path::new(FIXTURES_DIR).join(format!("{}.webp", name))
+}
+
+fn decode_rgba(webp: &[u8]) -> (i32, i32, Vec<u8>) {
+    let image = Image::from_encoded(Data::new_copy(webp)).expect("fixture/output is not a valid image");
+    let info = skia_safe::ImageInfo::new(image.dimensions(), ColorType::RGBA8888, skia_safe::AlphaType::Unpremul, None);
+    let mut pixels = vec![0u8; (image.width() * image.height() * 4) as usize];
+    let row_bytes = (image.width() * 4) as usize;
+    assert!(image.read_pixels(&info, &mut pixels, row_bytes, (0, 0), skia_safe::image::CachingHint::Disallow));
+    (image.width(), image.height(), pixels)
+}
+
+fn assert_images_match(actual: &[u8], expected: &[u8], name: &str) {
+    let (aw, ah, apixels) = decode_rgba(actual);
+    let (ew, eh, epixels) = decode_rgba(expected);
+
+    assert_eq!((aw, ah), (ew, eh), "{}: dimensions changed", name);
+
+    let mismatches = apixels.iter().zip(epixels.iter())
+        .filter(|(a, e)| (**a as i16 - **e as i16).unsigned_abs() as u8 > MAX_CHANNEL_DELTA)
+        .count();
+
+    assert_eq!(mismatches, 0, "{}: {} channel values differ by more than {}", name, mismatches, MAX_CHANNEL_DELTA);
+}
+
+#[test]
+#[ignore = "needs 3rdparty assets + UPDATE_FIXTURES=1 fixtures, see tests/fixtures/README.md"]
+fn render_matches_golden_images() {
+    let update = std::env::var_os("UPDATE_FIXTURES").is_some();
+    let suffixes = Suffixes::default();
+
+    for (name, number) in CASES {
+        let picture = render_raw_number(*number, &suffixes)
+            .unwrap_or_else(|e| panic!("{}: render_raw_number({}) failed: {}", name, number, e));
+
+        let path = fixture_path(name);
+
+        if update {
+            std::fs::write(&path, &picture).unwrap_or_else(|e| panic!("{}: failed to write fixture: {}", name, e));
+            continue;
+        }
+
+        let expected = std::fs::read(&path)
+            .unwrap_or_else(|e| panic!("{}: missing fixture at {:?} ({}); run with UPDATE_FIXTURES=1 to generate it", name, path, e));
+
+        assert_images_match(&picture, &expected, name);
+    }
+}